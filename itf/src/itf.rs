@@ -2,11 +2,15 @@ use std::{
     collections::{HashMap, HashSet},
     fmt,
     hash::Hash,
+    marker::PhantomData,
     ops::{Deref, DerefMut},
 };
 
 use num_bigint::BigInt;
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{
+    de::{self, DeserializeOwned, MapAccess, SeqAccess, Visitor},
+    Deserialize, Serialize,
+};
 
 pub type ItfMap<K, V> = Itf<HashMap<K, V>>;
 pub type ItfSet<T> = Itf<HashSet<T>>;
@@ -57,6 +61,12 @@ impl<T> DerefMut for Itf<T> {
     }
 }
 
+#[derive(Deserialize)]
+struct Set<T> {
+    #[serde(rename = "#set")]
+    set: Vec<T>,
+}
+
 impl<'de, T> Deserialize<'de> for Itf<HashSet<T>>
 where
     T: Eq + Hash + Deserialize<'de>,
@@ -65,17 +75,95 @@ where
     where
         D: serde::Deserializer<'de>,
     {
-        #[derive(Deserialize)]
-        pub struct Set<T> {
-            #[serde(rename = "#set")]
-            set: Vec<T>,
-        }
-
         let set = Set::<T>::deserialize(deserializer)?;
         Ok(Self(set.set.into_iter().collect()))
     }
 }
 
+/// Decodes a set into a vector sorted according to the `Ord` instance of its elements,
+/// so that the resulting order does not depend on the order in which the trace lists them.
+impl<'de, T> Deserialize<'de> for Itf<Vec<T>>
+where
+    T: Ord + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut set = Set::<T>::deserialize(deserializer)?.set;
+        set.sort();
+        set.dedup();
+        Ok(Self(set))
+    }
+}
+
+/// Visitor for a sequence of elements, given either as a plain list or wrapped
+/// in a single-key object such as `{ "#map": [...] }`, with one of the given keys.
+struct SeqVisitor<T> {
+    keys: &'static [&'static str],
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> SeqVisitor<T> {
+    fn new(keys: &'static [&'static str], expecting: &'static str) -> Self {
+        Self {
+            keys,
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for SeqVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+        Ok(elements)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut elements = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match self.keys.iter().find(|&&k| k == key) {
+                Some(&k) if elements.is_some() => return Err(de::Error::duplicate_field(k)),
+                Some(_) => elements = Some(map.next_value()?),
+                None => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        elements.ok_or_else(|| {
+            let keys = self
+                .keys
+                .iter()
+                .map(|k| format!("`{}`", k))
+                .collect::<Vec<_>>();
+
+            de::Error::custom(format_args!("missing field {}", keys.join(" or ")))
+        })
+    }
+}
+
 impl<'de, K, V> Deserialize<'de> for Itf<HashMap<K, V>>
 where
     K: Eq + Hash + DeserializeOwned,
@@ -85,14 +173,8 @@ where
     where
         D: serde::Deserializer<'de>,
     {
-        #[derive(Deserialize)]
-        pub struct Map<K, V> {
-            #[serde(rename = "#map")]
-            elements: Vec<(K, V)>,
-        }
-
-        let map = Map::<K, V>::deserialize(deserializer)?;
-        Ok(Self(map.elements.into_iter().collect()))
+        let elements = decode_map_entries::<D, K, V>(deserializer)?;
+        Ok(Self(elements.into_iter().collect()))
     }
 }
 
@@ -101,10 +183,19 @@ impl<'de> Deserialize<'de> for Itf<BigInt> {
     where
         D: serde::Deserializer<'de>,
     {
+        // Some producers emit the value of `#bigint` as a JSON number rather than a string
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Str(#[serde(with = "crate::util::serde::display_from_str")] num_bigint::BigInt),
+            Int(i64),
+            UInt(u64),
+        }
+
         #[derive(Deserialize)]
         struct BI {
-            #[serde(rename = "#bigint", with = "crate::util::serde::display_from_str")]
-            value: num_bigint::BigInt,
+            #[serde(rename = "#bigint")]
+            value: Repr,
         }
 
         #[derive(Deserialize)]
@@ -117,18 +208,138 @@ impl<'de> Deserialize<'de> for Itf<BigInt> {
         IntOrBigInt::deserialize(deserializer)
             .map(|ib| match ib {
                 IntOrBigInt::Int(n) => BigInt::from(n),
-                IntOrBigInt::BigInt(b) => b.value,
+                IntOrBigInt::BigInt(BI { value }) => match value {
+                    Repr::Str(n) => n,
+                    Repr::Int(n) => BigInt::from(n),
+                    Repr::UInt(n) => BigInt::from(n),
+                },
             })
             .map(Itf)
     }
 }
 
+/// Decodes an integer or a `#bigint` directly into a [`num_bigint::BigInt`].
+///
+/// Use with `#[serde(deserialize_with = "itf::decode_bigint")]`.
+pub fn decode_bigint<'de, D>(deserializer: D) -> Result<BigInt, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    ItfBigInt::deserialize(deserializer).map(Itf::value)
+}
+
+impl Serialize for Itf<BigInt> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("#bigint", &self.0.to_string())?;
+        map.end()
+    }
+}
+
 #[derive(Deserialize)]
 struct Tup {
     #[serde(rename = "#tup")]
     elements: Vec<serde_json::Value>,
 }
 
+/// Decodes a homogeneous tuple of any length into a vector.
+///
+/// Tuples are otherwise only supported up to 7 elements through `Itf<(A, B, ...)>`;
+/// use this with `#[serde(deserialize_with = "itf::decode_tuple_vec")]` for larger ones.
+pub fn decode_tuple_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    struct TupVec<T> {
+        #[serde(rename = "#tup")]
+        elements: Vec<T>,
+    }
+
+    TupVec::<T>::deserialize(deserializer).map(|t| t.elements)
+}
+
+/// Decodes a map into the list of its entries, in the order in which they appear in the trace.
+///
+/// Use with `#[serde(deserialize_with = "itf::decode_map_entries")]`.
+pub fn decode_map_entries<'de, D, K, V>(deserializer: D) -> Result<Vec<(K, V)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    deserializer.deserialize_any(SeqVisitor::new(
+        &["#map"],
+        "a `#map` object or a list of key/value pairs",
+    ))
+}
+
+/// Decodes a relation, ie. a set of pairs, into a map.
+/// Fails if the relation is not a function, ie. if two pairs share the same key.
+///
+/// Use with `#[serde(deserialize_with = "itf::decode_relation_as_map")]`.
+pub fn decode_relation_as_map<'de, D, K, V>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    K: Eq + Hash + DeserializeOwned,
+    V: DeserializeOwned,
+{
+    let pairs = Set::<Itf<(K, V)>>::deserialize(deserializer)?.set;
+
+    let mut map = HashMap::with_capacity(pairs.len());
+    for Itf((k, v)) in pairs {
+        if map.insert(k, v).is_some() {
+            return Err(serde::de::Error::custom(
+                "expected a functional relation but found a repeated key",
+            ));
+        }
+    }
+
+    Ok(map)
+}
+
+/// Decodes a list, a tuple or a set into a vector, whichever of these the trace uses.
+///
+/// Use with `#[serde(deserialize_with = "itf::decode_list")]`.
+pub fn decode_list<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_any(SeqVisitor::new(
+        &["#tup", "#set"],
+        "a list, a `#tup` object or a `#set` object",
+    ))
+}
+
+/// Decodes a variant tagged with `Ok` or `Err`, ie. `{ "tag": "Ok", "value": ... }`, into a [`Result`].
+///
+/// Use with `#[serde(deserialize_with = "itf::decode_result")]`.
+pub fn decode_result<'de, D, T, E>(deserializer: D) -> Result<Result<T, E>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+    E: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(tag = "tag", content = "value")]
+    enum Variant<T, E> {
+        Ok(T),
+        Err(E),
+    }
+
+    Variant::<T, E>::deserialize(deserializer).map(|variant| match variant {
+        Variant::Ok(t) => Ok(t),
+        Variant::Err(e) => Err(e),
+    })
+}
+
 macro_rules! deserialize_itf_tuple {
     ($len:literal, $($n:literal $ty:ident)+) => {
         impl<'de, $($ty ,)+> Deserialize<'de> for Itf<($($ty ,)+)>
@@ -159,6 +370,7 @@ macro_rules! deserialize_itf_tuple {
     };
 }
 
+deserialize_itf_tuple!(1,  0 A);
 deserialize_itf_tuple!(2,  0 A 1 B);
 deserialize_itf_tuple!(3,  0 A 1 B 2 C);
 deserialize_itf_tuple!(4,  0 A 1 B 2 C 3 D);
@@ -198,6 +410,16 @@ mod tests {
         assert_eq!(set.0, elems);
     }
 
+    #[test]
+    fn deserialize_set_sorted_vec() {
+        let json = json!({
+            "#set": [3, 1, 4, 2]
+        });
+
+        let set: Itf<Vec<u64>> = serde_json::from_value(json).unwrap();
+        assert_eq!(set.value(), vec![1, 2, 3, 4]);
+    }
+
     #[test]
     fn deserialize_map() {
         let json = json!({
@@ -212,6 +434,179 @@ mod tests {
         assert_eq!(set.0, elems);
     }
 
+    #[test]
+    fn deserialize_map_record_keys() {
+        #[derive(Debug, PartialEq, Eq, Hash, Deserialize)]
+        struct Key {
+            node: String,
+            round: i64,
+        }
+
+        let json = json!({
+            "#map": [
+                [{ "node": "a", "round": 1 }, { "#set": [1, 2] }],
+                [{ "node": "b", "round": 1 }, { "#set": [] }],
+            ]
+        });
+
+        let map: ItfMap<Key, ItfSet<ItfInt>> = serde_json::from_value(json).unwrap();
+
+        let key = |node: &str, round| Key {
+            node: node.to_string(),
+            round,
+        };
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(*map[&key("a", 1)], [1, 2].into_iter().collect());
+        assert!(map[&key("b", 1)].is_empty());
+    }
+
+    #[test]
+    fn deserialize_map_bool_keys() {
+        let json = json!({
+            "#map": [[true, 1], [false, 2]]
+        });
+
+        let map: ItfMap<bool, u64> = serde_json::from_value(json).unwrap();
+        let elems = [(true, 1), (false, 2)]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(map.0, elems);
+    }
+
+    #[test]
+    fn deserialize_nested_map() {
+        let json = json!({
+            "#map": [
+                ["alice", { "#map": [["atom", { "#bigint": "100" }], ["osmo", 5]] }],
+                ["bob", { "#map": [] }],
+            ]
+        });
+
+        let map: ItfMap<ItfString, ItfMap<ItfString, ItfBigInt>> =
+            serde_json::from_value(json).unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["alice"].len(), 2);
+        assert_eq!(map["alice"]["atom"], Itf(BigInt::from(100)));
+        assert_eq!(map["alice"]["osmo"], Itf(BigInt::from(5)));
+        assert!(map["bob"].is_empty());
+    }
+
+    #[test]
+    fn deserialize_map_from_pairs() {
+        let json = json!([[1, "hello"], [2, "world"]]);
+
+        let map: ItfMap<u64, ItfString> = serde_json::from_value(json).unwrap();
+        let elems = [(1, "hello".to_string()), (2, "world".to_string())]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(map.0, elems);
+    }
+
+    #[test]
+    fn deserialize_map_errors() {
+        let json = json!({ "#map": [[1]] });
+        let err = serde_json::from_value::<ItfMap<u64, ItfString>>(json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid length 1, expected a tuple of size 2"
+        );
+
+        let json = json!({ "#map": [[1, 2]] });
+        let err = serde_json::from_value::<ItfMap<u64, ItfString>>(json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `2`, expected a string"
+        );
+
+        let json = json!({ "#map": [["a", { "#map": [["b", { "#map": [[1, "x"]] }]] }]] });
+        let err = serde_json::from_value::<ItfMap<String, ItfMap<String, ItfMap<u64, u64>>>>(json)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid type: string \"x\", expected u64");
+
+        let json = json!({ "#set": [[1, "a"]] });
+        let err = serde_json::from_value::<ItfMap<u64, ItfString>>(json).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `#map`");
+
+        let json = json!(1);
+        let err = serde_json::from_value::<ItfMap<u64, ItfString>>(json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid type: integer `1`, expected a `#map` object or a list of key/value pairs"
+        );
+    }
+
+    #[test]
+    fn deserialize_map_extra_keys() {
+        let elems = [(1, "a".to_string())]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        let json = json!({ "#map": [[1, "a"]], "extra": 1 });
+        let map: ItfMap<u64, ItfString> = serde_json::from_value(json).unwrap();
+        assert_eq!(map.0, elems);
+
+        let json = json!({ "extra": { "nested": [1] }, "#map": [[1, "a"]] });
+        let map: ItfMap<u64, ItfString> = serde_json::from_value(json).unwrap();
+        assert_eq!(map.0, elems);
+    }
+
+    #[test]
+    fn deserialize_map_from_non_pairs() {
+        let json = json!([[1, "hello"], [2]]);
+        assert!(serde_json::from_value::<ItfMap<u64, ItfString>>(json).is_err());
+
+        let json = json!([1, 2]);
+        assert!(serde_json::from_value::<ItfMap<u64, ItfString>>(json).is_err());
+    }
+
+    #[test]
+    fn deserialize_map_entries() {
+        #[derive(Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "decode_map_entries")]
+            entries: Vec<(u64, String)>,
+        }
+
+        let json = json!({
+            "entries": { "#map": [[3, "c"], [1, "a"], [2, "b"]] }
+        });
+
+        let s: S = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            s.entries,
+            vec![
+                (3, "c".to_string()),
+                (1, "a".to_string()),
+                (2, "b".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_map_entries_errors() {
+        #[derive(Debug, Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "decode_map_entries")]
+            #[allow(dead_code)]
+            entries: Vec<(u64, String)>,
+        }
+
+        let json = json!({ "entries": { "#map": [[1, "a"], [2]] } });
+        let err = serde_json::from_value::<S>(json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid length 1, expected a tuple of size 2"
+        );
+
+        let json = json!({ "entries": [[1, "a"], ["b", "c"]] });
+        let err = serde_json::from_value::<S>(json).unwrap_err();
+        assert_eq!(err.to_string(), "invalid type: string \"b\", expected u64");
+    }
+
     #[test]
     fn deserialize_bigint_int() {
         let json = json!(1024);
@@ -230,6 +625,77 @@ mod tests {
         assert_eq!(bigint.0, "1234567891011121314151617181920".parse().unwrap());
     }
 
+    #[test]
+    fn deserialize_num_bigint() {
+        #[derive(Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "decode_bigint")]
+            large: BigInt,
+            #[serde(deserialize_with = "decode_bigint")]
+            small: BigInt,
+        }
+
+        let json = json!({
+            "large": { "#bigint": "-1234567891011121314151617181920" },
+            "small": 42,
+        });
+
+        let s: S = serde_json::from_value(json).unwrap();
+        assert_eq!(s.large, "-1234567891011121314151617181920".parse().unwrap());
+        assert_eq!(s.small, BigInt::from(42));
+    }
+
+    #[test]
+    fn deserialize_bigint_number() {
+        let from_str: ItfBigInt = serde_json::from_value(json!({ "#bigint": "123" })).unwrap();
+        let from_num: ItfBigInt = serde_json::from_value(json!({ "#bigint": 123 })).unwrap();
+        assert_eq!(from_str, from_num);
+        assert_eq!(from_num.0, BigInt::from(123));
+
+        let negative: ItfBigInt = serde_json::from_value(json!({ "#bigint": -123 })).unwrap();
+        assert_eq!(negative.0, BigInt::from(-123));
+
+        let large: ItfBigInt = serde_json::from_value(json!({ "#bigint": u64::MAX })).unwrap();
+        assert_eq!(large.0, BigInt::from(u64::MAX));
+    }
+
+    #[test]
+    fn serialize_bigint() {
+        let bigint: ItfBigInt = Itf("-1234567891011121314151617181920".parse().unwrap());
+
+        let json = serde_json::to_value(&bigint).unwrap();
+        assert_eq!(
+            json,
+            json!({ "#bigint": "-1234567891011121314151617181920" })
+        );
+
+        let decoded: ItfBigInt = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, bigint);
+    }
+
+    #[test]
+    fn serialize_bigint_in_struct() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Coin {
+            amount: ItfBigInt,
+            denom: String,
+        }
+
+        let coin = Coin {
+            amount: Itf(BigInt::from(u64::MAX) * 2),
+            denom: "atom".to_string(),
+        };
+
+        let json = serde_json::to_string(&coin).unwrap();
+        assert_eq!(
+            json,
+            r##"{"amount":{"#bigint":"36893488147419103230"},"denom":"atom"}"##
+        );
+
+        let decoded: Coin = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, coin);
+    }
+
     #[test]
     #[should_panic(expected = "expected tuple with 3 elements but found 2")]
     fn deserialize_tuple_wrong_cardinality() {
@@ -244,6 +710,17 @@ mod tests {
             serde_json::from_value(json).unwrap();
     }
 
+    #[test]
+    fn deserialize_tuple_1() {
+        let json = json!({ "#tup": [42] });
+
+        let tuple: ItfTuple<(u64,)> = serde_json::from_value(json).unwrap();
+        assert_eq!(tuple.value(), (42,));
+
+        let json = json!({ "#tup": [42, 43] });
+        assert!(serde_json::from_value::<ItfTuple<(u64,)>>(json).is_err());
+    }
+
     #[test]
     fn deserialize_tuple_2() {
         let json = json!({
@@ -414,6 +891,116 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_tuple_vec() {
+        #[derive(Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "decode_tuple_vec")]
+            tuple: Vec<u64>,
+        }
+
+        let json = json!({
+            "tuple": { "#tup": (1..=20).collect::<Vec<u64>>() }
+        });
+
+        let s: S = serde_json::from_value(json).unwrap();
+        assert_eq!(s.tuple, (1..=20).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn deserialize_relation_as_map() {
+        #[derive(Debug, Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "decode_relation_as_map")]
+            relation: HashMap<u64, String>,
+        }
+
+        let json = json!({
+            "relation": { "#set": [{ "#tup": [1, "a"] }, { "#tup": [2, "b"] }] }
+        });
+
+        let s: S = serde_json::from_value(json).unwrap();
+        let elems = [(1, "a".to_string()), (2, "b".to_string())]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(s.relation, elems);
+
+        let json = json!({
+            "relation": { "#set": [{ "#tup": [1, "a"] }, { "#tup": [1, "b"] }] }
+        });
+
+        let err = serde_json::from_value::<S>(json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected a functional relation but found a repeated key"
+        );
+    }
+
+    #[test]
+    fn deserialize_list() {
+        #[derive(Debug, Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "decode_list")]
+            elements: Vec<u64>,
+        }
+
+        let inputs = [
+            json!({ "elements": [1, 2, 3] }),
+            json!({ "elements": { "#tup": [1, 2, 3] } }),
+            json!({ "elements": { "#set": [1, 2, 3] } }),
+        ];
+
+        for json in inputs {
+            let s: S = serde_json::from_value(json).unwrap();
+            assert_eq!(s.elements, vec![1, 2, 3]);
+        }
+
+        let json = json!({ "elements": { "#map": [[1, 2]] } });
+        let err = serde_json::from_value::<S>(json).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `#tup` or `#set`");
+
+        let inputs = [
+            json!({ "elements": [1, "two", 3] }),
+            json!({ "elements": { "#tup": [1, "two", 3] } }),
+            json!({ "elements": { "#set": [1, "two", 3] } }),
+        ];
+
+        for json in inputs {
+            let err = serde_json::from_value::<S>(json).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "invalid type: string \"two\", expected u64"
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_result() {
+        #[derive(Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "decode_result")]
+            outcome: Result<ItfBigInt, String>,
+        }
+
+        let json = json!({
+            "outcome": { "tag": "Ok", "value": { "#bigint": "100" } }
+        });
+        let s: S = serde_json::from_value(json).unwrap();
+        assert_eq!(s.outcome, Ok(Itf(BigInt::from(100))));
+
+        let json = json!({
+            "outcome": { "tag": "Err", "value": "INSUFFICIENT_FUNDS" }
+        });
+        let s: S = serde_json::from_value(json).unwrap();
+        assert_eq!(s.outcome, Err("INSUFFICIENT_FUNDS".to_string()));
+
+        let json = json!({
+            "outcome": { "tag": "Maybe", "value": 1 }
+        });
+        assert!(serde_json::from_value::<S>(json).is_err());
+    }
+
     #[test]
     fn display() {
         let s = "1234567891011121314151617181920";
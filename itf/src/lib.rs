@@ -7,7 +7,7 @@
 //! ```rust
 //! use serde::Deserialize;
 //!
-//! use itf::{trace_from_str, ItfMap, ItfSet};
+//! use itf::{trace_from_str, ItfMap, ItfSet, Trace};
 //!
 //! #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
 //! enum Bank {
@@ -53,7 +53,7 @@
 //!
 //! **Output:**
 //!
-//! ```rust
+//! ```text
 //! trace = Trace {
 //!     meta: TraceMeta {
 //!         description: None,
@@ -215,6 +215,9 @@ mod trace;
 use serde::{de::DeserializeOwned, Deserialize};
 pub use trace::*;
 
+mod plain;
+pub use plain::*;
+
 use serde_json::Result;
 
 pub fn trace_from_str<'a, State>(s: &'a str) -> Result<Trace<State>>
@@ -303,4 +306,21 @@ mod tests {
     fn from_reader() {
         let _trace = trace_from_reader::<State, _>(DATA.as_bytes()).unwrap();
     }
+
+    #[test]
+    fn from_str_borrowed() {
+        use std::borrow::Cow;
+
+        #[derive(Debug, Deserialize)]
+        struct State<'a> {
+            #[serde(borrow)]
+            bank_of_boat: Cow<'a, str>,
+        }
+
+        let trace = trace_from_str::<State>(DATA).unwrap();
+
+        for state in trace.states {
+            assert!(matches!(state.value.bank_of_boat, Cow::Borrowed(_)));
+        }
+    }
 }
@@ -23,7 +23,7 @@ pub struct TraceMeta {
     pub timestamp: Option<u64>,
 
     #[serde(flatten)]
-    pub other: HashMap<String, String>,
+    pub other: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,5 +32,5 @@ pub struct StateMeta {
     pub index: Option<u64>,
 
     #[serde(flatten)]
-    pub other: HashMap<String, String>,
+    pub other: HashMap<String, serde_json::Value>,
 }
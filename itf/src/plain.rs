@@ -0,0 +1,69 @@
+use serde_json::Value;
+
+/// Converts an untyped ITF value into plain JSON, without any of the ITF sentinels.
+///
+/// The conversion is lossy: bigints become strings, sets and tuples become arrays,
+/// maps become arrays of `[key, value]` pairs, and unserializable values become `null`.
+pub fn to_plain_json(value: &Value) -> Value {
+    match value {
+        Value::Array(elements) => Value::Array(elements.iter().map(to_plain_json).collect()),
+        Value::Object(fields) => match sentinel(fields) {
+            Some(("#bigint", Value::String(n))) => Value::String(n.clone()),
+            Some(("#bigint", n)) => Value::String(n.to_string()),
+            Some(("#set" | "#tup" | "#map", elements)) => to_plain_json(elements),
+            Some(("#unserializable", _)) => Value::Null,
+            _ => Value::Object(
+                fields
+                    .iter()
+                    .map(|(k, v)| (k.clone(), to_plain_json(v)))
+                    .collect(),
+            ),
+        },
+        _ => value.clone(),
+    }
+}
+
+fn sentinel(fields: &serde_json::Map<String, Value>) -> Option<(&str, &Value)> {
+    if fields.len() != 1 {
+        return None;
+    }
+
+    fields
+        .iter()
+        .next()
+        .filter(|(k, _)| k.starts_with('#'))
+        .map(|(k, v)| (k.as_str(), v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn plain_json() {
+        let value = json!({
+            "balance": { "#bigint": "1234567891011121314151617181920" },
+            "small": { "#bigint": 12 },
+            "owners": { "#set": ["alice", "bob"] },
+            "pair": { "#tup": [1, { "#set": [] }] },
+            "ledger": { "#map": [["alice", { "#bigint": "1" }], ["bob", 2]] },
+            "nested": { "list": [{ "#tup": [true] }], "flag": false },
+            "opaque": { "#set": [{ "#unserializable": "Int" }] },
+        });
+
+        assert_eq!(
+            to_plain_json(&value),
+            json!({
+                "balance": "1234567891011121314151617181920",
+                "small": "12",
+                "owners": ["alice", "bob"],
+                "pair": [1, []],
+                "ledger": [["alice", "1"], ["bob", 2]],
+                "nested": { "list": [[true]], "flag": false },
+                "opaque": [null],
+            })
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
+use thiserror::Error;
 
 use crate::{StateMeta, TraceMeta};
 
@@ -28,6 +31,283 @@ pub struct Trace<S> {
     pub states: Vec<State<S>>,
 }
 
+impl State<serde_json::Value> {
+    /// Returns a copy of this state restricted to the given variables.
+    /// Variables which are not present in the state are skipped.
+    pub fn project(&self, vars: &[&str]) -> Self {
+        let value = vars
+            .iter()
+            .filter_map(|&var| Some((var.to_string(), self.value.get(var)?.clone())))
+            .collect::<serde_json::Map<_, _>>();
+
+        Self {
+            meta: self.meta.clone(),
+            value: serde_json::Value::Object(value),
+        }
+    }
+
+    /// Returns a copy of this state restricted to the given variables,
+    /// or an error naming the first of these variables which is not present in the state.
+    pub fn try_project(&self, vars: &[&str]) -> Result<Self, MissingVariable> {
+        match vars.iter().find(|&&var| self.value.get(var).is_none()) {
+            Some(var) => Err(MissingVariable(var.to_string())),
+            None => Ok(self.project(vars)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("missing variable `{0}`")]
+pub struct MissingVariable(pub String);
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invariant violated in state {index}")]
+pub struct InvariantViolation<S> {
+    pub index: usize,
+    pub state: S,
+}
+
+impl<S> Trace<S> {
+    /// Returns the position and value of the first state satisfying the given predicate.
+    pub fn find<F>(&self, pred: F) -> Option<(usize, &S)>
+    where
+        F: Fn(&S) -> bool,
+    {
+        self.states
+            .iter()
+            .enumerate()
+            .find(|(_, state)| pred(&state.value))
+            .map(|(index, state)| (index, &state.value))
+    }
+
+    /// Checks that the given invariant holds in every state of the trace,
+    /// and reports the first state in which it does not.
+    pub fn check_invariant<F>(&self, inv: F) -> Result<(), InvariantViolation<&S>>
+    where
+        F: Fn(&S) -> bool,
+    {
+        match self.find(|state| !inv(state)) {
+            Some((index, state)) => Err(InvariantViolation { index, state }),
+            None => Ok(()),
+        }
+    }
+
+    /// Greedily removes states from the trace as long as the given predicate still holds,
+    /// eg. to shrink a counterexample down to the states needed to reproduce it.
+    ///
+    /// The first and last states are always kept, as well as the state the trace
+    /// loops back to, if any. Kept states retain their original metadata.
+    pub fn minimize<F>(&self, still_fails: F) -> Self
+    where
+        S: Clone,
+        F: Fn(&Self) -> bool,
+    {
+        let mut trace = self.clone();
+        let mut i = 1;
+
+        while i + 1 < trace.states.len() {
+            if trace.loop_index == Some(i as u64) {
+                i += 1;
+                continue;
+            }
+
+            let mut candidate = trace.clone();
+            candidate.states.remove(i);
+            candidate.loop_index = trace
+                .loop_index
+                .map(|l| if l > i as u64 { l - 1 } else { l });
+
+            if still_fails(&candidate) {
+                trace = candidate;
+            } else {
+                i += 1;
+            }
+        }
+
+        trace
+    }
+
+    /// Removes stuttering steps from the trace, ie. states equal to the state preceding them.
+    /// The first state of each run of equal states is kept, along with its metadata,
+    /// and the loop index, if any, is updated to point to the state kept for its run.
+    pub fn dedup_stutter(&mut self)
+    where
+        S: PartialEq,
+    {
+        let states = std::mem::take(&mut self.states);
+        let mut loop_index = self.loop_index;
+
+        for (i, state) in states.into_iter().enumerate() {
+            let stutter = matches!(self.states.last(), Some(prev) if prev.value == state.value);
+            if !stutter {
+                self.states.push(state);
+            }
+
+            if self.loop_index == Some(i as u64) {
+                loop_index = Some(self.states.len() as u64 - 1);
+            }
+        }
+
+        self.loop_index = loop_index;
+    }
+}
+
+impl Trace<serde_json::Value> {
+    /// Returns the value of the given variable in each state of the trace,
+    /// or `None` for the states in which the variable is absent.
+    pub fn history(&self, var: &str) -> Vec<Option<&serde_json::Value>> {
+        self.states
+            .iter()
+            .map(|state| state.value.get(var))
+            .collect()
+    }
+
+    /// Decodes the value of the given variable in each state of the trace.
+    pub fn decode_variable<'a, T>(&'a self, var: &str) -> serde_json::Result<Vec<T>>
+    where
+        T: Deserialize<'a>,
+    {
+        self.states
+            .iter()
+            .enumerate()
+            .map(|(i, state)| match state.value.get(var) {
+                Some(value) => T::deserialize(value).map_err(|e| {
+                    serde::de::Error::custom(format_args!(
+                        "invalid variable `{}` in state {}: {}",
+                        var, i, e
+                    ))
+                }),
+                None => Err(serde::de::Error::custom(format_args!(
+                    "missing variable `{}` in state {}",
+                    var, i
+                ))),
+            })
+            .collect()
+    }
+
+    /// Returns one column per state variable, with one row per state.
+    /// Rows for states in which a variable is absent are filled with `null`.
+    pub fn columns(&self) -> HashMap<String, Vec<serde_json::Value>> {
+        let mut columns = HashMap::new();
+
+        for state in &self.states {
+            for var in state.value.as_object().into_iter().flat_map(|o| o.keys()) {
+                columns.entry(var.clone()).or_insert_with(|| {
+                    self.history(var)
+                        .into_iter()
+                        .map(|value| value.cloned().unwrap_or_default())
+                        .collect()
+                });
+            }
+        }
+
+        columns
+    }
+
+    /// Renders the trace as a Graphviz graph, with one node per state and one edge
+    /// per transition, labelled with the variables changed by that transition.
+    pub fn to_dot(&self) -> String {
+        use std::fmt::Write;
+
+        let mut dot = String::from("digraph trace {\n");
+
+        for (i, state) in self.states.iter().enumerate() {
+            let label = state.meta.index.unwrap_or(i as u64);
+            writeln!(dot, "  s{} [label=\"{}\"];", i, label).unwrap();
+        }
+
+        for (i, step) in self.states.windows(2).enumerate() {
+            let changed = changed_vars(&step[0].value, &step[1].value).join(", ");
+            let label = changed.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(dot, "  s{} -> s{} [label=\"{}\"];", i, i + 1, label).unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Checks that no state of the trace contains an unserializable value,
+    /// and otherwise reports the index and path of all such values.
+    pub fn check_serializable(&self) -> Result<(), Vec<(usize, String)>> {
+        let mut found = Vec::new();
+
+        for (i, state) in self.states.iter().enumerate() {
+            let mut paths = Vec::new();
+            unserializable_paths(&state.value, String::new(), &mut paths);
+            found.extend(paths.into_iter().map(|path| (i, path)));
+        }
+
+        if found.is_empty() {
+            Ok(())
+        } else {
+            Err(found)
+        }
+    }
+}
+
+fn unserializable_paths(value: &serde_json::Value, path: String, paths: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(fields) if fields.contains_key("#unserializable") => {
+            paths.push(path)
+        }
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                unserializable_paths(value, path, paths);
+            }
+        }
+        serde_json::Value::Array(elements) => {
+            for (i, value) in elements.iter().enumerate() {
+                unserializable_paths(value, format!("{}[{}]", path, i), paths);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn changed_vars<'a>(prev: &'a serde_json::Value, next: &'a serde_json::Value) -> Vec<&'a str> {
+    let keys = |value: &'a serde_json::Value| value.as_object().into_iter().flat_map(|o| o.keys());
+
+    let mut vars = keys(prev)
+        .chain(keys(next))
+        .filter(|&var| prev.get(var) != next.get(var))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    vars.sort_unstable();
+    vars.dedup();
+    vars
+}
+
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+}
+
+/// Counts the variables added, removed and modified between two untyped states.
+///
+/// Values are compared as JSON, so a set or a map whose elements are listed
+/// in a different order counts as modified.
+pub fn diff_summary(old: &serde_json::Value, new: &serde_json::Value) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+
+    for var in changed_vars(old, new) {
+        match (old.get(var), new.get(var)) {
+            (None, _) => summary.added += 1,
+            (_, None) => summary.removed += 1,
+            _ => summary.modified += 1,
+        }
+    }
+
+    summary
+}
+
 impl<S> Default for Trace<S> {
     fn default() -> Self {
         Self {
@@ -44,6 +324,23 @@ impl<S> Default for Trace<S> {
 mod tests {
     use super::*;
 
+    use serde_json::json;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+    struct Counter {
+        counter: i64,
+    }
+
+    fn counter_trace(counters: &[i64]) -> Trace<Counter> {
+        let states = counters
+            .iter()
+            .enumerate()
+            .map(|(i, counter)| json!({ "#meta": { "index": i }, "counter": counter }))
+            .collect::<Vec<_>>();
+
+        serde_json::from_value(json!({ "#meta": {}, "states": states })).unwrap()
+    }
+
     #[test]
     fn trace_default() {
         #[derive(Debug, PartialEq, Eq)]
@@ -56,4 +353,497 @@ mod tests {
         assert_eq!(t.loop_index, None);
         assert_eq!(t.states, Vec::<State<S>>::new());
     }
+
+    #[test]
+    fn state_seq_collections() {
+        use std::collections::{LinkedList, VecDeque};
+
+        #[derive(Debug, Deserialize)]
+        struct S {
+            queue: VecDeque<u64>,
+            names: LinkedList<String>,
+        }
+
+        let json = json!({
+            "#meta": { "index": 0 },
+            "queue": [1, 2, 3],
+            "names": ["alice", "bob"],
+        });
+
+        let state: State<S> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(state.value.queue, VecDeque::from([1, 2, 3]));
+        assert_eq!(
+            state.value.names,
+            LinkedList::from(["alice".to_string(), "bob".to_string()])
+        );
+    }
+
+    #[test]
+    fn state_smart_pointers() {
+        use std::sync::Arc;
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Inner {
+            balance: crate::ItfBigInt,
+            owners: crate::ItfSet<String>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct S {
+            boxed: Box<Inner>,
+            shared: Arc<Inner>,
+        }
+
+        let inner = json!({ "balance": { "#bigint": "10" }, "owners": { "#set": ["alice"] } });
+        let json = json!({
+            "#meta": { "index": 0 },
+            "boxed": inner,
+            "shared": inner,
+        });
+
+        let state: State<S> = serde_json::from_value(json).unwrap();
+        let expected: Inner = serde_json::from_value(inner).unwrap();
+
+        assert_eq!(*state.value.boxed, expected);
+        assert_eq!(*state.value.shared, expected);
+    }
+
+    #[test]
+    fn state_adjacently_tagged() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(tag = "t", content = "c")]
+        enum Action {
+            Init,
+            Send {
+                to: String,
+                amount: crate::ItfBigInt,
+            },
+            Burn(crate::ItfSet<i64>),
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct S {
+            actions: Vec<Action>,
+        }
+
+        let json = json!({
+            "#meta": { "index": 0 },
+            "actions": [
+                { "t": "Init" },
+                { "c": { "amount": { "#bigint": "5" }, "to": "bob" }, "t": "Send" },
+                { "t": "Burn", "c": { "#set": [1, 2] } },
+            ],
+        });
+
+        let state: State<S> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(state.value.actions.len(), 3);
+        assert_eq!(state.value.actions[0], Action::Init);
+        assert_eq!(
+            state.value.actions[1],
+            Action::Send {
+                to: "bob".to_string(),
+                amount: 5.into()
+            }
+        );
+        assert!(matches!(&state.value.actions[2], Action::Burn(set) if set.len() == 2));
+    }
+
+    #[test]
+    fn state_deserialize_with() {
+        use serde::Deserializer;
+
+        fn hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            (0..s.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(serde::de::Error::custom))
+                .collect()
+        }
+
+        fn sum<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+            Vec::<i64>::deserialize(deserializer).map(|v| v.iter().sum())
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct S {
+            #[serde(deserialize_with = "hex")]
+            hash: Vec<u8>,
+            #[serde(deserialize_with = "sum")]
+            total: i64,
+        }
+
+        let json = json!({
+            "#meta": { "index": 0 },
+            "hash": "deadbeef",
+            "total": [1, 2, 3, 4],
+        });
+
+        let state: State<S> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(state.value.hash, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(state.value.total, 10);
+    }
+
+    #[test]
+    fn trace_meta_other() {
+        let json = json!({
+            "#meta": { "source": "Spec.tla", "generated": { "by": "apalache" }, "steps": 3 },
+            "states": [],
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(trace.meta.source.as_deref(), Some("Spec.tla"));
+        assert_eq!(trace.meta.other["generated"], json!({ "by": "apalache" }));
+        assert_eq!(trace.meta.other["steps"], json!(3));
+    }
+
+    #[test]
+    fn state_meta_other() {
+        let json = json!({
+            "#meta": { "index": 1, "action": "send", "reason": { "step": 1 } },
+            "counter": 1,
+        });
+
+        let state: State<serde_json::Value> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(state.meta.index, Some(1));
+        assert_eq!(state.meta.other["action"], json!("send"));
+        assert_eq!(state.meta.other["reason"], json!({ "step": 1 }));
+        assert_eq!(state.value, json!({ "counter": 1 }));
+    }
+
+    #[test]
+    fn project() {
+        let json = json!({
+            "#meta": { "index": 3 },
+            "a": 1,
+            "b": { "#set": [1, 2] },
+            "c": "hello",
+            "d": true,
+        });
+
+        let state: State<serde_json::Value> = serde_json::from_value(json).unwrap();
+        let projected = state.project(&["b", "d", "missing"]);
+
+        assert_eq!(projected.meta, state.meta);
+        assert_eq!(
+            projected.value,
+            json!({ "b": { "#set": [1, 2] }, "d": true })
+        );
+
+        assert_eq!(
+            state.try_project(&["b", "d", "missing"]),
+            Err(MissingVariable("missing".to_string()))
+        );
+        assert_eq!(
+            MissingVariable("missing".to_string()).to_string(),
+            "missing variable `missing`"
+        );
+
+        let projected = state.try_project(&["a", "c"]).unwrap();
+        assert_eq!(projected.meta, state.meta);
+        assert_eq!(projected.value, json!({ "a": 1, "c": "hello" }));
+    }
+
+    #[test]
+    fn find() {
+        let trace = counter_trace(&[0, 5, 12, 20]);
+
+        assert_eq!(
+            trace.find(|s| s.counter > 10),
+            Some((2, &Counter { counter: 12 }))
+        );
+        assert_eq!(trace.find(|s| s.counter > 100), None);
+    }
+
+    #[test]
+    fn check_invariant() {
+        let trace = counter_trace(&[0, 1, 2, 3]);
+
+        assert_eq!(trace.check_invariant(|s| s.counter < 4), Ok(()));
+
+        let violation = trace.check_invariant(|s| s.counter < 2).unwrap_err();
+        assert_eq!(violation.index, 2);
+        assert_eq!(violation.state, &Counter { counter: 2 });
+        assert_eq!(violation.to_string(), "invariant violated in state 2");
+    }
+
+    #[test]
+    fn minimize() {
+        let mut trace = counter_trace(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        trace.loop_index = Some(4);
+
+        let minimized = trace.minimize(|t| t.find(|s| s.counter == 2).is_some());
+
+        let indices = minimized
+            .states
+            .iter()
+            .map(|s| s.meta.index)
+            .collect::<Vec<_>>();
+
+        assert_eq!(indices, vec![Some(0), Some(2), Some(4), Some(7)]);
+        assert_eq!(minimized.loop_index, Some(2));
+    }
+
+    #[test]
+    fn dedup_stutter() {
+        let json = json!({
+            "#meta": {},
+            "states": [
+                { "#meta": { "index": 0 }, "counter": 0, "set": { "#set": [1, 2] } },
+                { "#meta": { "index": 1 }, "counter": 1, "set": { "#set": [1, 2] } },
+                { "#meta": { "index": 2 }, "counter": 1, "set": { "#set": [2, 1] } },
+                { "#meta": { "index": 3 }, "counter": 1, "set": { "#set": [1, 2] } },
+                { "#meta": { "index": 4 }, "counter": 0, "set": { "#set": [1, 2] } },
+            ]
+        });
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct S {
+            counter: i64,
+            set: crate::ItfSet<i64>,
+        }
+
+        let mut trace: Trace<S> = serde_json::from_value(json).unwrap();
+        trace.dedup_stutter();
+
+        let indices = trace
+            .states
+            .iter()
+            .map(|s| s.meta.index)
+            .collect::<Vec<_>>();
+
+        assert_eq!(indices, vec![Some(0), Some(1), Some(4)]);
+    }
+
+    #[test]
+    fn dedup_stutter_loop() {
+        let mut trace = counter_trace(&[0, 0, 1]);
+        trace.loop_index = Some(2);
+        trace.dedup_stutter();
+
+        assert_eq!(trace.states.len(), 2);
+        assert_eq!(trace.loop_index, Some(1));
+
+        let mut trace = counter_trace(&[0, 0, 1, 1, 2, 3]);
+        trace.loop_index = Some(3);
+        trace.dedup_stutter();
+
+        let indices = trace
+            .states
+            .iter()
+            .map(|s| s.meta.index)
+            .collect::<Vec<_>>();
+
+        assert_eq!(indices, vec![Some(0), Some(2), Some(4), Some(5)]);
+        assert_eq!(trace.loop_index, Some(1));
+
+        let mut trace = counter_trace(&[0, 1, 1, 2]);
+        trace.loop_index = Some(3);
+        trace.dedup_stutter();
+
+        assert_eq!(trace.loop_index, Some(2));
+    }
+
+    #[test]
+    fn history() {
+        let json = json!({
+            "#meta": {},
+            "vars": ["counter", "flag"],
+            "states": [
+                { "#meta": { "index": 0 }, "counter": 0, "flag": false },
+                { "#meta": { "index": 1 }, "counter": 1 },
+                { "#meta": { "index": 2 }, "counter": 2, "flag": true },
+            ]
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            trace.history("counter"),
+            vec![Some(&json!(0)), Some(&json!(1)), Some(&json!(2))]
+        );
+        assert_eq!(
+            trace.history("flag"),
+            vec![Some(&json!(false)), None, Some(&json!(true))]
+        );
+        assert_eq!(trace.history("missing"), vec![None, None, None]);
+    }
+
+    #[test]
+    fn to_dot() {
+        let json = json!({
+            "#meta": {},
+            "states": [
+                { "#meta": { "index": 0 }, "x": 0, "y": 0 },
+                { "#meta": { "index": 1 }, "x": 1, "y": 0 },
+                { "#meta": { "index": 2 }, "x": 2, "y": 1 },
+                { "#meta": { "index": 3 }, "x": 2, "y": 1 },
+            ]
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+        let dot = trace.to_dot();
+
+        assert_eq!(dot.matches(" -> ").count(), 3);
+        assert_eq!(
+            dot,
+            "digraph trace {\n\
+             \x20 s0 [label=\"0\"];\n\
+             \x20 s1 [label=\"1\"];\n\
+             \x20 s2 [label=\"2\"];\n\
+             \x20 s3 [label=\"3\"];\n\
+             \x20 s0 -> s1 [label=\"x\"];\n\
+             \x20 s1 -> s2 [label=\"x, y\"];\n\
+             \x20 s2 -> s3 [label=\"\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn to_dot_escapes_labels() {
+        let json = json!({
+            "#meta": {},
+            "states": [
+                { "#meta": { "index": 0 }, "say \"hi\"": 0, "back\\slash": 0, "esc\u{1b}": 0 },
+                { "#meta": { "index": 1 }, "say \"hi\"": 1, "back\\slash": 1, "esc\u{1b}": 1 },
+            ]
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+
+        assert!(trace
+            .to_dot()
+            .contains("  s0 -> s1 [label=\"back\\\\slash, esc\u{1b}, say \\\"hi\\\"\"];\n"));
+    }
+
+    #[test]
+    fn diff_summary() {
+        let old = json!({
+            "record": { "owner": "alice", "balance": 1 },
+            "set": { "#set": [1, 2] },
+            "map": { "#map": [["a", 1]] },
+            "same": 0,
+            "gone": true,
+        });
+
+        let new = json!({
+            "record": { "owner": "alice", "balance": 2 },
+            "set": { "#set": [1, 2, 3] },
+            "map": { "#map": [["a", 1], ["b", 2]] },
+            "same": 0,
+            "fresh": "x",
+        });
+
+        assert_eq!(
+            super::diff_summary(&old, &new),
+            DiffSummary {
+                added: 1,
+                removed: 1,
+                modified: 3,
+            }
+        );
+
+        assert_eq!(super::diff_summary(&new, &new), DiffSummary::default());
+    }
+
+    #[test]
+    fn check_serializable() {
+        let json = json!({
+            "#meta": {},
+            "states": [
+                { "#meta": { "index": 0 }, "x": 0, "f": { "#set": [] } },
+                {
+                    "#meta": { "index": 1 },
+                    "x": { "#unserializable": "Int" },
+                    "f": { "#set": [1] },
+                },
+                { "#meta": { "index": 2 }, "x": 2, "f": { "#set": [] } },
+                {
+                    "#meta": { "index": 3 },
+                    "x": 3,
+                    "f": { "#set": [1, { "#unserializable": "Nat" }] },
+                },
+            ]
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            trace.check_serializable(),
+            Err(vec![(1, "x".to_string()), (3, "f.#set[1]".to_string())])
+        );
+
+        let trace = Trace {
+            states: vec![trace.states[0].clone(), trace.states[2].clone()],
+            ..trace
+        };
+
+        assert_eq!(trace.check_serializable(), Ok(()));
+    }
+
+    #[test]
+    fn decode_variable() {
+        let json = json!({
+            "#meta": {},
+            "states": [
+                { "#meta": { "index": 0 }, "balance": 10, "owner": "alice" },
+                { "#meta": { "index": 1 }, "balance": 7, "owner": "alice" },
+                { "#meta": { "index": 2 }, "balance": 12 },
+            ]
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+
+        let balances: Vec<u64> = trace.decode_variable("balance").unwrap();
+        assert_eq!(balances, vec![10, 7, 12]);
+
+        let owners = trace.decode_variable::<&str>("owner").unwrap_err();
+        assert_eq!(owners.to_string(), "missing variable `owner` in state 2");
+
+        let json = json!({
+            "#meta": {},
+            "states": [
+                { "#meta": { "index": 0 }, "balance": 10 },
+                { "#meta": { "index": 1 }, "balance": "oops" },
+            ]
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+
+        let err = trace.decode_variable::<u64>("balance").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid variable `balance` in state 1: invalid type: string \"oops\", expected u64"
+        );
+    }
+
+    #[test]
+    fn columns() {
+        let json = json!({
+            "#meta": {},
+            "vars": ["counter", "flag"],
+            "states": [
+                { "#meta": { "index": 0 }, "counter": 0, "flag": false },
+                { "#meta": { "index": 1 }, "counter": 1 },
+                { "#meta": { "index": 2 }, "counter": { "#bigint": "2" }, "flag": true },
+            ]
+        });
+
+        let trace: Trace<serde_json::Value> = serde_json::from_value(json).unwrap();
+        let columns = trace.columns();
+
+        assert_eq!(columns.len(), 2);
+        assert_eq!(
+            columns["counter"],
+            vec![json!(0), json!(1), json!({ "#bigint": "2" })]
+        );
+        assert_eq!(
+            columns["flag"],
+            vec![json!(false), json!(null), json!(true)]
+        );
+    }
 }